@@ -0,0 +1,81 @@
+//! A tiny in-process publish/subscribe primitive used to fan a single
+//! message out to many connected clients.
+//!
+//! This is deliberately minimal: it does not depend on an async runtime,
+//! so it can be used from handlers running on Iron's synchronous worker
+//! threads.
+
+use std::sync::mpsc;
+use std::sync::Mutex;
+
+/// Fans a `String` message out to every currently subscribed receiver.
+///
+/// Subscribers that have been dropped (e.g. because the client
+/// disconnected) are pruned the next time a message is broadcast.
+pub struct Broadcaster {
+    subscribers: Mutex<Vec<mpsc::Sender<String>>>,
+}
+
+impl Broadcaster {
+    /// Create a new `Broadcaster` with no subscribers.
+    pub fn new() -> Self {
+        Broadcaster {
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Register a new subscriber and return the receiving end of its
+    /// channel.
+    pub fn subscribe(&self) -> mpsc::Receiver<String> {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers
+            .lock()
+            .expect("broadcaster subscriber lock poisoned")
+            .push(tx);
+        rx
+    }
+
+    /// Send `message` to every live subscriber, dropping any whose
+    /// receiving end has gone away.
+    pub fn broadcast(&self, message: String) {
+        let mut subscribers = self
+            .subscribers
+            .lock()
+            .expect("broadcaster subscriber lock poisoned");
+        subscribers.retain(|tx| tx.send(message.clone()).is_ok());
+    }
+}
+
+impl Default for Broadcaster {
+    fn default() -> Self {
+        Broadcaster::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delivers_to_all_subscribers() {
+        let broadcaster = Broadcaster::new();
+        let rx1 = broadcaster.subscribe();
+        let rx2 = broadcaster.subscribe();
+
+        broadcaster.broadcast("hello".to_string());
+
+        assert_eq!(rx1.recv().unwrap(), "hello");
+        assert_eq!(rx2.recv().unwrap(), "hello");
+    }
+
+    #[test]
+    fn prunes_dropped_subscribers() {
+        let broadcaster = Broadcaster::new();
+        {
+            let _rx = broadcaster.subscribe();
+        }
+        assert_eq!(broadcaster.subscribers.lock().unwrap().len(), 1);
+        broadcaster.broadcast("ping".to_string());
+        assert_eq!(broadcaster.subscribers.lock().unwrap().len(), 0);
+    }
+}
@@ -0,0 +1,126 @@
+//! Builder for assembling a `SpaceapiServer` with its optional features
+//! (status modifiers, the live event stream) before it starts listening.
+
+use std::sync::Arc;
+use std::thread;
+
+use crate::api;
+use crate::broadcaster::Broadcaster;
+use crate::events::{self, EventStreamConfig, EventStreamHandler, StatusProvider};
+use crate::modifiers::{self, AsyncStatusModifier, RedisPool, StatusModifier};
+
+/// Incrementally configures a `SpaceapiServer`.
+///
+/// ```ignore
+/// let server = SpaceapiServerBuilder::new(listen, status, redis)
+///     .add_modifier(Box::new(StateFromPeopleNowPresent::new()))
+///     .enable_event_stream("/events")
+///     .build()?;
+/// ```
+pub struct SpaceapiServerBuilder {
+    listen: String,
+    status: api::Status,
+    redis_url: String,
+    modifiers: Vec<Box<dyn StatusModifier>>,
+    async_modifiers: Vec<Box<dyn AsyncStatusModifier>>,
+    event_stream: Option<EventStreamConfig>,
+}
+
+impl SpaceapiServerBuilder {
+    /// Create a new builder for a server listening on `listen`, serving
+    /// `status` as its base status and reading sensor values from the
+    /// Redis instance at `redis_url`.
+    pub fn new<L: Into<String>, R: Into<String>>(listen: L, status: api::Status, redis_url: R) -> Self {
+        SpaceapiServerBuilder {
+            listen: listen.into(),
+            status,
+            redis_url: redis_url.into(),
+            modifiers: Vec::new(),
+            async_modifiers: Vec::new(),
+            event_stream: None,
+        }
+    }
+
+    /// Register a `StatusModifier` to run after sensors are read.
+    pub fn add_modifier(mut self, modifier: Box<dyn StatusModifier>) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+
+    /// Register an `AsyncStatusModifier` to run, in registration order,
+    /// after sensors are read and all sync modifiers have run.
+    pub fn add_async_modifier(mut self, modifier: Box<dyn AsyncStatusModifier>) -> Self {
+        self.async_modifiers.push(modifier);
+        self
+    }
+
+    /// Register a Server-Sent Events endpoint at `path`. Connected
+    /// clients receive a freshly-rendered status every time a sensor
+    /// value changes in Redis, instead of having to poll `/`.
+    pub fn enable_event_stream<S: Into<String>>(mut self, path: S) -> Self {
+        self.event_stream = Some(EventStreamConfig { path: path.into() });
+        self
+    }
+
+    /// Finish building and construct the `SpaceapiServer`.
+    pub fn build(self) -> Result<crate::server::SpaceapiServer, crate::errors::SpaceapiServerError> {
+        crate::server::SpaceapiServer::from_builder(self)
+    }
+
+    pub(crate) fn listen(&self) -> &str {
+        &self.listen
+    }
+
+    pub(crate) fn status(&self) -> &api::Status {
+        &self.status
+    }
+
+    pub(crate) fn redis_url(&self) -> &str {
+        &self.redis_url
+    }
+
+    pub(crate) fn modifiers(&self) -> &[Box<dyn StatusModifier>] {
+        &self.modifiers
+    }
+
+    pub(crate) fn async_modifiers(&self) -> &[Box<dyn AsyncStatusModifier>] {
+        &self.async_modifiers
+    }
+
+    /// Run every registered `AsyncStatusModifier` against `status`, in
+    /// registration order. Called by the per-request handler after the
+    /// sync modifiers have already run.
+    pub(crate) fn run_async_modifiers(&self, status: &mut api::Status, redis: &RedisPool) {
+        modifiers::run_async_modifiers(&self.async_modifiers, status, redis);
+    }
+
+    pub(crate) fn event_stream(&self) -> Option<&EventStreamConfig> {
+        self.event_stream.as_ref()
+    }
+
+    /// If an event stream was enabled via `enable_event_stream`,
+    /// construct its `Broadcaster`, spawn the background watcher thread
+    /// that feeds it, and build the `Handler` to register at
+    /// `event_stream().path`. Returns `None` if no event stream was
+    /// configured.
+    ///
+    /// `rebuild_status` must mirror the per-request handler: read
+    /// sensors from Redis and run all registered modifiers. Called by
+    /// `SpaceapiServer::from_builder`, which owns mounting the returned
+    /// handler on the router and keeping the returned `JoinHandle`
+    /// alive for the server's lifetime.
+    pub(crate) fn spawn_event_stream(
+        &self,
+        rebuild_status: StatusProvider,
+    ) -> Option<(Arc<Broadcaster>, thread::JoinHandle<()>, EventStreamHandler)> {
+        self.event_stream.as_ref()?;
+        let broadcaster = Arc::new(Broadcaster::new());
+        let watcher = events::spawn_status_watcher(
+            self.redis_url.clone(),
+            Arc::clone(&broadcaster),
+            rebuild_status.clone(),
+        );
+        let handler = EventStreamHandler::new(Arc::clone(&broadcaster), rebuild_status);
+        Some((broadcaster, watcher, handler))
+    }
+}
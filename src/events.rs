@@ -0,0 +1,180 @@
+//! Server-Sent Events (SSE) support for pushing live status changes.
+//!
+//! Instead of polling `/`, a client can connect to the path registered
+//! via `SpaceapiServerBuilder::enable_event_stream` and receive a fresh
+//! `data: <status json>` frame immediately on connect, then another one
+//! every time a sensor value changes.
+//!
+//! The update endpoint publishes to a Redis pub/sub channel whenever it
+//! writes a sensor value; [`spawn_status_watcher`] subscribes to that
+//! channel, re-runs the sensor-read + `StatusModifier` pipeline, and
+//! broadcasts the freshly-serialized status to every connected client
+//! via a [`Broadcaster`].
+
+use std::io;
+use std::io::Write;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+
+use iron::headers::ContentType;
+use iron::response::WriteBody;
+use iron::Handler;
+use iron::{status, IronResult, Request, Response};
+use log::error;
+
+use crate::api;
+use crate::broadcaster::Broadcaster;
+use crate::modifiers::RedisPool;
+
+/// Name of the Redis pub/sub channel that the dynamic-update endpoint
+/// publishes to whenever it writes a sensor value.
+pub const SENSOR_UPDATE_CHANNEL: &str = "spaceapi:sensor-updates";
+
+/// Publish a sensor-update notification on [`SENSOR_UPDATE_CHANNEL`].
+///
+/// The dynamic sensor-update endpoint must call this after writing a
+/// new sensor value to Redis, so that [`spawn_status_watcher`] picks up
+/// the change and fans a freshly-rendered status out to every connected
+/// SSE client. Without this call the event stream only ever emits its
+/// per-connection initial frame.
+pub fn publish_sensor_update(redis_pool: &RedisPool) -> redis::RedisResult<()> {
+    let mut connection = redis_pool.get().map_err(|err| {
+        error!("event stream: could not get a pooled connection to publish: {}", err);
+        redis::RedisError::from((redis::ErrorKind::IoError, "pool exhausted"))
+    })?;
+    redis::cmd("PUBLISH")
+        .arg(SENSOR_UPDATE_CHANNEL)
+        .arg(1)
+        .query(&mut *connection)
+}
+
+/// Configuration for the optional event stream endpoint.
+#[derive(Clone, Debug)]
+pub struct EventStreamConfig {
+    /// The path the SSE endpoint is registered under, e.g. `/events`.
+    pub path: String,
+}
+
+/// Produces the current, freshly-rendered status on demand, mirroring
+/// the per-request handler logic (read sensors, run all modifiers).
+pub type StatusProvider = Arc<dyn Fn() -> api::Status + Send + Sync>;
+
+/// Owns a subscriber's channel and writes each message it receives to
+/// the response body as an SSE `data:` frame, blocking until the
+/// connection is closed.
+///
+/// Iron's `WriteBody` is only implemented for a handful of concrete
+/// types (`String`, `Vec<u8>`, `File`, ...), not arbitrary closures, so
+/// this is its own type rather than a boxed `FnMut`.
+struct SseStream {
+    initial: Option<String>,
+    receiver: mpsc::Receiver<String>,
+}
+
+impl WriteBody for SseStream {
+    fn write_body(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        if let Some(initial) = self.initial.take() {
+            write!(out, "data: {}\n\n", initial)?;
+            out.flush()?;
+        }
+        for message in self.receiver.iter() {
+            write!(out, "data: {}\n\n", message)?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Iron handler that subscribes a new client to the `Broadcaster` and
+/// streams every subsequent status update to it as an SSE `data:` frame.
+///
+/// A newly connected client also immediately receives one frame with
+/// the current status, so the "open/closed" badge has something to
+/// show before the next sensor change.
+pub struct EventStreamHandler {
+    broadcaster: Arc<Broadcaster>,
+    current_status: StatusProvider,
+}
+
+impl EventStreamHandler {
+    /// Create a new handler backed by `broadcaster`, using
+    /// `current_status` to render the initial frame sent to each newly
+    /// connected client.
+    pub fn new(broadcaster: Arc<Broadcaster>, current_status: StatusProvider) -> Self {
+        EventStreamHandler {
+            broadcaster,
+            current_status,
+        }
+    }
+}
+
+impl Handler for EventStreamHandler {
+    fn handle(&self, _req: &mut Request) -> IronResult<Response> {
+        let receiver = self.broadcaster.subscribe();
+        let initial = match serde_json::to_string(&(self.current_status)()) {
+            Ok(serialized) => Some(serialized),
+            Err(err) => {
+                error!("event stream: could not serialize initial status: {}", err);
+                None
+            }
+        };
+
+        let mut response = Response::with(status::Ok);
+        response
+            .headers
+            .set(ContentType("text/event-stream".parse().unwrap()));
+        response.body = Some(Box::new(SseStream { initial, receiver }));
+        Ok(response)
+    }
+}
+
+/// Spawn a background thread that watches `SENSOR_UPDATE_CHANNEL` on
+/// Redis and, on every message, re-runs the sensor-read + modifier
+/// pipeline and broadcasts the result to `broadcaster`.
+///
+/// `rebuild_status` mirrors the per-request handler logic: read sensors
+/// from Redis and apply all registered `StatusModifier`s.
+pub fn spawn_status_watcher(
+    redis_url: String,
+    broadcaster: Arc<Broadcaster>,
+    rebuild_status: StatusProvider,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let client = match redis::Client::open(redis_url.as_str()) {
+            Ok(client) => client,
+            Err(err) => {
+                error!("event stream: could not connect to redis: {}", err);
+                return;
+            }
+        };
+        let connection = match client.get_connection() {
+            Ok(connection) => connection,
+            Err(err) => {
+                error!("event stream: could not open pub/sub connection: {}", err);
+                return;
+            }
+        };
+        let mut pubsub = connection.as_pubsub();
+        if let Err(err) = pubsub.subscribe(SENSOR_UPDATE_CHANNEL) {
+            error!("event stream: could not subscribe to redis channel: {}", err);
+            return;
+        }
+
+        loop {
+            match pubsub.get_message() {
+                Ok(_) => {
+                    let current_status = rebuild_status();
+                    match serde_json::to_string(&current_status) {
+                        Ok(serialized) => broadcaster.broadcast(serialized),
+                        Err(err) => error!("event stream: could not serialize status: {}", err),
+                    }
+                }
+                Err(err) => {
+                    error!("event stream: lost connection to redis pub/sub: {}", err);
+                    break;
+                }
+            }
+        }
+    })
+}
@@ -1,6 +1,12 @@
 //! Modifiers which can be injected by the application logic to change the
 //! state dynamically per request.
 
+use std::collections::HashMap;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
 use crate::api;
 
 /// `StatusModifier`s are used to modify the status
@@ -9,9 +15,165 @@ pub trait StatusModifier: Send + Sync {
     fn modify(&self, status: &mut api::Status);
 }
 
+/// Pooled Redis connections handed to `AsyncStatusModifier`s.
+pub type RedisPool = r2d2::Pool<r2d2_redis::RedisConnectionManager>;
+
+/// Like `StatusModifier`, but runs asynchronously and is given access to
+/// the server's Redis connection pool.
+///
+/// This lets a modifier pull in derived data it couldn't compute from
+/// the status alone (recent check-in history, a cached "next event"
+/// string, a rate-limited external lookup). The futures are driven by
+/// `run_async_modifiers`, which blocks the Iron worker thread serving the
+/// request until each one resolves. Async modifiers are registered in
+/// the builder next to the existing sync `StatusModifier`s and run, in
+/// registration order, after all sensors are read and the sync
+/// modifiers have run.
+#[async_trait]
+pub trait AsyncStatusModifier: Send + Sync {
+    /// Called after all registered sensors are read and all sync
+    /// `StatusModifier`s have run.
+    async fn modify_with(&self, status: &mut api::Status, redis: &RedisPool);
+}
+
+thread_local! {
+    // A dedicated single-threaded runtime per Iron worker thread. Each
+    // worker already blocks for the duration of a request, so blocking
+    // it further to drive these futures doesn't cost us anything we
+    // weren't already paying; it just avoids spinning up a fresh
+    // runtime on every request.
+    static ASYNC_MODIFIER_RUNTIME: tokio::runtime::Runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build async modifier runtime");
+}
+
+/// Runs every modifier in `modifiers`, in order, against `status`,
+/// blocking the calling (Iron worker) thread until each future
+/// resolves.
+///
+/// This is the execution path that lets `AsyncStatusModifier`s plug
+/// into Iron's otherwise-synchronous request handling: rather than
+/// making the whole server async, each worker thread drives the
+/// futures on its own thread-local single-threaded runtime.
+pub fn run_async_modifiers(
+    modifiers: &[Box<dyn AsyncStatusModifier>],
+    status: &mut api::Status,
+    redis: &RedisPool,
+) {
+    ASYNC_MODIFIER_RUNTIME.with(|runtime| {
+        for modifier in modifiers {
+            runtime.block_on(modifier.modify_with(status, redis));
+        }
+    });
+}
+
+/// Formats the `state.message` string for a given people-present count.
+///
+/// Implement this to localize or correctly pluralize the "N people here
+/// right now" text instead of reimplementing `StatusModifier` from
+/// scratch.
+pub trait MessageFormatter: Send + Sync {
+    /// Return the message for `count` people present, or `None` to
+    /// leave `state.message` unchanged.
+    fn format(&self, count: u64) -> Option<String>;
+}
+
+/// Reproduces the crate's original English text: "N person/people here
+/// right now", leaving the message unchanged when nobody is present.
+pub struct EnglishMessageFormatter;
+
+impl MessageFormatter for EnglishMessageFormatter {
+    fn format(&self, count: u64) -> Option<String> {
+        match count {
+            0 => None,
+            1 => Some(format!("{} person here right now", count)),
+            _ => Some(format!("{} people here right now", count)),
+        }
+    }
+}
+
+/// A CLDR plural category
+/// (<http://cldr.unicode.org/index/cldr-spec/plural-rules>). Most
+/// languages only use a subset of these; English, for example, only
+/// distinguishes `One` from `Other`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+/// Selects the CLDR plural category a count falls into for some locale.
+pub trait PluralRule: Send + Sync {
+    /// Return the plural category `count` falls into.
+    fn category(&self, count: u64) -> PluralCategory;
+}
+
+/// A `MessageFormatter` that selects a message template by CLDR plural
+/// category instead of the `count == 1` / `count > 1` split, so
+/// languages with more than two plural forms (e.g. Polish's
+/// `one`/`few`/`many`/`other`) render correctly.
+pub struct CldrMessageFormatter {
+    rule: Box<dyn PluralRule>,
+    templates: HashMap<PluralCategory, String>,
+}
+
+impl CldrMessageFormatter {
+    /// Create a formatter that looks up `count`'s plural category via
+    /// `rule` and renders the matching template from `templates`,
+    /// substituting `{count}` with the actual count.
+    ///
+    /// `templates` should have an entry for `PluralCategory::Other`,
+    /// used as the fallback when `rule` returns a category with no
+    /// template of its own.
+    pub fn new(rule: Box<dyn PluralRule>, templates: HashMap<PluralCategory, String>) -> Self {
+        CldrMessageFormatter { rule, templates }
+    }
+}
+
+impl MessageFormatter for CldrMessageFormatter {
+    fn format(&self, count: u64) -> Option<String> {
+        let category = self.rule.category(count);
+        let template = self
+            .templates
+            .get(&category)
+            .or_else(|| self.templates.get(&PluralCategory::Other))?;
+        Some(template.replace("{count}", &count.to_string()))
+    }
+}
+
 /// This modifier updates the opening state based on the
 /// first people now present sensor (if present).
-pub struct StateFromPeopleNowPresent;
+pub struct StateFromPeopleNowPresent {
+    formatter: Box<dyn MessageFormatter>,
+}
+
+impl StateFromPeopleNowPresent {
+    /// Create a modifier using the default English message formatter,
+    /// reproducing the crate's original behavior.
+    pub fn new() -> Self {
+        StateFromPeopleNowPresent {
+            formatter: Box::new(EnglishMessageFormatter),
+        }
+    }
+
+    /// Create a modifier that renders `state.message` with a custom
+    /// `MessageFormatter`, e.g. to localize and correctly pluralize the
+    /// "N people here" text for non-English spaces.
+    pub fn with_formatter(formatter: Box<dyn MessageFormatter>) -> Self {
+        StateFromPeopleNowPresent { formatter }
+    }
+}
+
+impl Default for StateFromPeopleNowPresent {
+    fn default() -> Self {
+        StateFromPeopleNowPresent::new()
+    }
+}
 
 impl StatusModifier for StateFromPeopleNowPresent {
     fn modify(&self, status: &mut api::Status) {
@@ -23,19 +185,247 @@ impl StatusModifier for StateFromPeopleNowPresent {
             .map(|sensor: &api::PeopleNowPresentSensor| sensor.value);
         if let Some(count) = people_now_present {
             status.state.open = Some(count > 0);
-            if count == 1 {
-                status.state.message = Some(format!("{} person here right now", count));
-            } else if count > 1 {
-                status.state.message = Some(format!("{} people here right now", count));
+            if let Some(message) = self.formatter.format(count) {
+                status.state.message = Some(message);
+            }
+        }
+    }
+}
+
+/// Policy used by `StateFromSensors` to decide whether a space counts
+/// as open from its door-lock and people-present sensors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpenPolicy {
+    /// Open if any door is unlocked, regardless of people present. With
+    /// no doors configured, open if someone is present.
+    AnyDoorUnlocked,
+    /// Open only if at least one door is unlocked AND someone is
+    /// present.
+    UnlockedAndPeoplePresent,
+}
+
+/// A single door's lock state.
+///
+/// The pinned `spaceapi` version's `Sensors` only exposes
+/// `people_now_present` and `temperature`, with no `door_locked`
+/// category yet, so door state can't be read off `api::Status` the way
+/// people-present sensors can. `StateFromSensors` instead takes the door
+/// states once, in `StateFromSensors::new`, and uses that same `Vec` on
+/// every request; it has no way to update them afterwards.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DoorState {
+    /// Human-readable door name, e.g. "front door".
+    pub name: String,
+    /// Whether the door is currently unlocked.
+    pub unlocked: bool,
+}
+
+/// Derives `status.state.open` from door-lock and people-present
+/// sensors combined, rather than trusting only the first
+/// `people_now_present` entry.
+///
+/// This is useful for spaces with multiple doors (e.g. a front and a
+/// back door), where the open/closed state should be an aggregate of
+/// every door's lock state rather than hand-rolled in application
+/// code.
+pub struct StateFromSensors {
+    policy: OpenPolicy,
+    doors: Vec<DoorState>,
+}
+
+impl StateFromSensors {
+    /// Create a modifier that aggregates `doors` and the people-present
+    /// sensor according to `policy`.
+    pub fn new(policy: OpenPolicy, doors: Vec<DoorState>) -> Self {
+        StateFromSensors { policy, doors }
+    }
+
+    fn door_summary(&self) -> Option<String> {
+        if self.doors.is_empty() {
+            return None;
+        }
+        let parts: Vec<String> = self
+            .doors
+            .iter()
+            .map(|door| {
+                let state = if door.unlocked { "unlocked" } else { "locked" };
+                format!("{} {}", door.name, state)
+            })
+            .collect();
+        Some(parts.join(", "))
+    }
+}
+
+impl StatusModifier for StateFromSensors {
+    fn modify(&self, status: &mut api::Status) {
+        // Door state is an independent input, not read off `status`, so
+        // it must still drive `open` even when there is no sensors
+        // object at all -- only the people-count branch depends on it.
+        let people_count: Option<u64> = status
+            .sensors
+            .as_ref()
+            .and_then(|sensors| sensors.people_now_present.first())
+            .map(|sensor| sensor.value);
+
+        let any_unlocked = self.doors.iter().any(|door| door.unlocked);
+        let people_present = people_count.map(|count| count > 0).unwrap_or(false);
+
+        if self.doors.is_empty() && people_count.is_none() {
+            return;
+        }
+
+        let open = match self.policy {
+            // With no door sensors configured, fall back to people
+            // presence rather than reporting closed just because there
+            // is nothing unlocked to find.
+            OpenPolicy::AnyDoorUnlocked => {
+                if self.doors.is_empty() {
+                    people_present
+                } else {
+                    any_unlocked
+                }
+            }
+            OpenPolicy::UnlockedAndPeoplePresent => any_unlocked && people_present,
+        };
+        status.state.open = Some(open);
+
+        let mut message_parts = Vec::new();
+        if let Some(door_summary) = self.door_summary() {
+            message_parts.push(door_summary);
+        }
+        if let Some(count) = people_count {
+            message_parts.push(format!(
+                "{} {} here",
+                count,
+                if count == 1 { "person" } else { "people" }
+            ));
+        }
+        if !message_parts.is_empty() {
+            status.state.message = Some(message_parts.join("; "));
+        }
+    }
+}
+
+/// Redis key holding the Unix timestamp (seconds) of the most recent
+/// sensor write. The dynamic-update endpoint sets this on every write,
+/// regardless of which sensor changed, so checking staleness is a
+/// single `GET` rather than a `KEYS` scan over one timestamp per
+/// sensor -- `KEYS` walks and blocks the entire Redis keyspace, which
+/// is not something we can afford on the `/` hot path.
+pub const LAST_UPDATE_KEY: &str = "sensors:last-update";
+
+/// Degrades the reported state to "unknown" once no sensor has reported
+/// a value for longer than `max_age`.
+///
+/// This modifier holds its own `RedisPool` so it can check
+/// `LAST_UPDATE_KEY` even though `StatusModifier::modify` only receives
+/// the status. It takes a connection from the pool rather than opening
+/// a fresh one, since `modify` runs once per `/` request.
+pub struct StaleSensorState {
+    max_age: Duration,
+    redis_pool: RedisPool,
+}
+
+impl StaleSensorState {
+    /// Create a modifier that marks the state unknown once
+    /// `LAST_UPDATE_KEY` is older than `max_age`.
+    pub fn new(max_age: Duration, redis_pool: RedisPool) -> Self {
+        StaleSensorState {
+            max_age,
+            redis_pool,
+        }
+    }
+
+    /// Age of the most recent sensor write, or `None` if it can't be
+    /// determined (no connection, or no sensor has ever reported one).
+    fn newest_update_age(&self) -> Option<Duration> {
+        let mut connection = match self.redis_pool.get() {
+            Ok(connection) => connection,
+            Err(err) => {
+                log::error!("stale sensor check: could not get a pooled connection: {}", err);
+                return None;
+            }
+        };
+        let newest: Option<u64> = match redis::cmd("GET")
+            .arg(LAST_UPDATE_KEY)
+            .query(&mut *connection)
+        {
+            Ok(newest) => newest,
+            Err(err) => {
+                log::error!(
+                    "stale sensor check: could not read {}: {}",
+                    LAST_UPDATE_KEY,
+                    err
+                );
+                return None;
             }
+        };
+        let newest = newest?;
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()?
+            .as_secs();
+        Some(Duration::from_secs(now.saturating_sub(newest)))
+    }
+}
+
+impl StatusModifier for StaleSensorState {
+    fn modify(&self, status: &mut api::Status) {
+        match self.newest_update_age() {
+            Some(age) if age > self.max_age => {
+                status.state.open = None;
+                status.state.message = Some(format!(
+                    "status unknown (no update for {} minutes)",
+                    age.as_secs() / 60
+                ));
+            }
+            _ => {}
         }
     }
 }
 
+/// Spawn a background thread that calls `sweep` once every `interval`,
+/// e.g. to re-check sensor staleness and republish the status without
+/// waiting for an incoming request. The function itself neither checks
+/// staleness nor publishes anything: that is all up to `sweep`.
+///
+/// Skips any ticks that were missed (e.g. because a previous sweep took
+/// longer than `interval`) rather than running them back-to-back.
+pub fn spawn_stale_sweep<F>(interval: Duration, mut sweep: F) -> thread::JoinHandle<()>
+where
+    F: FnMut() + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut next_tick = Instant::now() + interval;
+        loop {
+            let now = Instant::now();
+            if now < next_tick {
+                thread::sleep(next_tick - now);
+            }
+            sweep();
+            // Skip missed ticks instead of queueing a backlog of them.
+            next_tick += interval;
+            while next_tick <= Instant::now() {
+                next_tick += interval;
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn make_pnp_sensor(value: u64) -> api::PeopleNowPresentSensor {
+        api::PeopleNowPresentSensor {
+            location: None,
+            name: None,
+            names: None,
+            description: None,
+            value,
+        }
+    }
+
     mod state_from_people_now_present {
         use super::*;
 
@@ -44,7 +434,7 @@ mod tests {
             let mut status = api::Status::default();
             status.sensors = None;
             assert_eq!(status.state.message, None);
-            StateFromPeopleNowPresent.modify(&mut status);
+            StateFromPeopleNowPresent::new().modify(&mut status);
             assert_eq!(status.sensors, None);
             assert_eq!(status.state.message, None);
         }
@@ -57,20 +447,10 @@ mod tests {
                 temperature: vec![],
             });
             assert_eq!(status.state.message, None);
-            StateFromPeopleNowPresent.modify(&mut status);
+            StateFromPeopleNowPresent::new().modify(&mut status);
             assert_eq!(status.state.message, None);
         }
 
-        fn make_pnp_sensor(value: u64) -> api::PeopleNowPresentSensor {
-            api::PeopleNowPresentSensor {
-                location: None,
-                name: None,
-                names: None,
-                description: None,
-                value,
-            }
-        }
-
         #[test]
         fn zero_people_present() {
             let mut status = api::Status::default();
@@ -83,7 +463,7 @@ mod tests {
                 status.state.message,
                 Some("This will remain unchanged.".to_string())
             );
-            StateFromPeopleNowPresent.modify(&mut status);
+            StateFromPeopleNowPresent::new().modify(&mut status);
             assert_eq!(
                 status.state.message,
                 Some("This will remain unchanged.".to_string())
@@ -98,7 +478,7 @@ mod tests {
                 temperature: vec![],
             });
             assert_eq!(status.state.message, None);
-            StateFromPeopleNowPresent.modify(&mut status);
+            StateFromPeopleNowPresent::new().modify(&mut status);
             assert_eq!(status.state.message, Some("1 person here right now".to_string()));
         }
 
@@ -110,8 +490,229 @@ mod tests {
                 temperature: vec![],
             });
             assert_eq!(status.state.message, None);
-            StateFromPeopleNowPresent.modify(&mut status);
+            StateFromPeopleNowPresent::new().modify(&mut status);
             assert_eq!(status.state.message, Some("2 people here right now".to_string()));
         }
+
+        struct ConstantMessageFormatter;
+
+        impl MessageFormatter for ConstantMessageFormatter {
+            fn format(&self, _count: u64) -> Option<String> {
+                Some("custom message".to_string())
+            }
+        }
+
+        #[test]
+        fn with_formatter_uses_custom_formatter() {
+            let mut status = api::Status::default();
+            status.sensors = Some(api::Sensors {
+                people_now_present: vec![make_pnp_sensor(2)],
+                temperature: vec![],
+            });
+            StateFromPeopleNowPresent::with_formatter(Box::new(ConstantMessageFormatter))
+                .modify(&mut status);
+            assert_eq!(status.state.message, Some("custom message".to_string()));
+        }
+    }
+
+    mod async_status_modifier {
+        use super::*;
+
+        struct AppendModifier(&'static str);
+
+        #[async_trait]
+        impl AsyncStatusModifier for AppendModifier {
+            async fn modify_with(&self, status: &mut api::Status, _redis: &RedisPool) {
+                let mut message = status.state.message.clone().unwrap_or_default();
+                message.push_str(self.0);
+                status.state.message = Some(message);
+            }
+        }
+
+        fn unconnected_pool() -> RedisPool {
+            // `build_unchecked` doesn't eagerly open a connection, so
+            // this works without a running Redis -- fine here since
+            // `AppendModifier` never touches `redis`.
+            let manager = r2d2_redis::RedisConnectionManager::new("redis://127.0.0.1/")
+                .expect("invalid redis url");
+            r2d2::Pool::builder().build_unchecked(manager)
+        }
+
+        #[test]
+        fn runs_registered_modifiers_in_order() {
+            let pool = unconnected_pool();
+            let modifiers: Vec<Box<dyn AsyncStatusModifier>> =
+                vec![Box::new(AppendModifier("a")), Box::new(AppendModifier("b"))];
+            let mut status = api::Status::default();
+            run_async_modifiers(&modifiers, &mut status, &pool);
+            assert_eq!(status.state.message, Some("ab".to_string()));
+        }
+    }
+
+    mod cldr_message_formatter {
+        use super::*;
+
+        struct PolishPluralRule;
+
+        impl PluralRule for PolishPluralRule {
+            fn category(&self, count: u64) -> PluralCategory {
+                // Simplified Polish cardinal plural rule.
+                match count {
+                    1 => PluralCategory::One,
+                    n if n % 10 >= 2 && n % 10 <= 4 && !(n % 100 >= 12 && n % 100 <= 14) => {
+                        PluralCategory::Few
+                    }
+                    _ => PluralCategory::Many,
+                }
+            }
+        }
+
+        fn polish_formatter() -> CldrMessageFormatter {
+            let mut templates = HashMap::new();
+            templates.insert(PluralCategory::One, "{count} osoba tutaj".to_string());
+            templates.insert(PluralCategory::Few, "{count} osoby tutaj".to_string());
+            templates.insert(PluralCategory::Many, "{count} osób tutaj".to_string());
+            templates.insert(PluralCategory::Other, "{count} osób tutaj".to_string());
+            CldrMessageFormatter::new(Box::new(PolishPluralRule), templates)
+        }
+
+        #[test]
+        fn renders_one_category() {
+            assert_eq!(polish_formatter().format(1), Some("1 osoba tutaj".to_string()));
+        }
+
+        #[test]
+        fn renders_few_category() {
+            assert_eq!(polish_formatter().format(3), Some("3 osoby tutaj".to_string()));
+        }
+
+        #[test]
+        fn renders_many_category() {
+            assert_eq!(polish_formatter().format(12), Some("12 osób tutaj".to_string()));
+        }
+
+        #[test]
+        fn falls_back_to_other_when_category_has_no_template() {
+            let mut templates = HashMap::new();
+            templates.insert(PluralCategory::Other, "{count} osób tutaj".to_string());
+            let formatter = CldrMessageFormatter::new(Box::new(PolishPluralRule), templates);
+            assert_eq!(formatter.format(1), Some("1 osób tutaj".to_string()));
+        }
+    }
+
+    mod state_from_sensors {
+        use super::*;
+
+        fn make_door(name: &str, unlocked: bool) -> DoorState {
+            DoorState {
+                name: name.to_string(),
+                unlocked,
+            }
+        }
+
+        #[test]
+        fn no_sensors_and_no_doors_leaves_state_untouched() {
+            let mut status = api::Status::default();
+            status.sensors = None;
+            StateFromSensors::new(OpenPolicy::AnyDoorUnlocked, vec![]).modify(&mut status);
+            assert_eq!(status.state.open, None);
+        }
+
+        #[test]
+        fn no_sensors_but_doors_configured_still_derives_open() {
+            let mut status = api::Status::default();
+            status.sensors = None;
+            let doors = vec![make_door("front", true)];
+            StateFromSensors::new(OpenPolicy::AnyDoorUnlocked, doors).modify(&mut status);
+            assert_eq!(status.state.open, Some(true));
+        }
+
+        #[test]
+        fn any_door_unlocked_opens_regardless_of_people() {
+            let mut status = api::Status::default();
+            status.sensors = Some(api::Sensors {
+                people_now_present: vec![make_pnp_sensor(0)],
+                temperature: vec![],
+            });
+            let doors = vec![make_door("front", false), make_door("back", true)];
+            StateFromSensors::new(OpenPolicy::AnyDoorUnlocked, doors).modify(&mut status);
+            assert_eq!(status.state.open, Some(true));
+            assert_eq!(
+                status.state.message,
+                Some("front locked, back unlocked; 0 people here".to_string())
+            );
+        }
+
+        #[test]
+        fn no_doors_falls_back_to_people_presence() {
+            let mut status = api::Status::default();
+            status.sensors = Some(api::Sensors {
+                people_now_present: vec![make_pnp_sensor(2)],
+                temperature: vec![],
+            });
+            StateFromSensors::new(OpenPolicy::AnyDoorUnlocked, vec![]).modify(&mut status);
+            assert_eq!(status.state.open, Some(true));
+        }
+
+        #[test]
+        fn no_doors_and_no_people_closes() {
+            let mut status = api::Status::default();
+            status.sensors = Some(api::Sensors {
+                people_now_present: vec![make_pnp_sensor(0)],
+                temperature: vec![],
+            });
+            StateFromSensors::new(OpenPolicy::AnyDoorUnlocked, vec![]).modify(&mut status);
+            assert_eq!(status.state.open, Some(false));
+        }
+
+        #[test]
+        fn unlocked_and_people_present_requires_both() {
+            let mut status = api::Status::default();
+            status.sensors = Some(api::Sensors {
+                people_now_present: vec![make_pnp_sensor(0)],
+                temperature: vec![],
+            });
+            let doors = vec![make_door("front", true)];
+            StateFromSensors::new(OpenPolicy::UnlockedAndPeoplePresent, doors).modify(&mut status);
+            assert_eq!(status.state.open, Some(false));
+        }
+
+        #[test]
+        fn unlocked_and_people_present_opens_when_both_true() {
+            let mut status = api::Status::default();
+            status.sensors = Some(api::Sensors {
+                people_now_present: vec![make_pnp_sensor(3)],
+                temperature: vec![],
+            });
+            let doors = vec![make_door("front", true)];
+            StateFromSensors::new(OpenPolicy::UnlockedAndPeoplePresent, doors).modify(&mut status);
+            assert_eq!(status.state.open, Some(true));
+            assert_eq!(
+                status.state.message,
+                Some("front unlocked; 3 people here".to_string())
+            );
+        }
+    }
+
+    mod stale_sweep {
+        use super::*;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        #[test]
+        fn ticks_and_skips_missed_intervals() {
+            let ticks = Arc::new(AtomicUsize::new(0));
+            let ticks_clone = Arc::clone(&ticks);
+            spawn_stale_sweep(Duration::from_millis(10), move || {
+                ticks_clone.fetch_add(1, Ordering::SeqCst);
+                // Simulate a slow sweep that overruns one interval, which
+                // should be absorbed rather than triggering a burst of
+                // queued catch-up ticks.
+                thread::sleep(Duration::from_millis(25));
+            });
+            thread::sleep(Duration::from_millis(60));
+            let count = ticks.load(Ordering::SeqCst);
+            assert!(count >= 1 && count <= 3, "unexpected tick count: {}", count);
+        }
     }
 }